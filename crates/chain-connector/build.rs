@@ -0,0 +1,129 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+
+/// ABI JSON for the on-chain contracts nox calls. One [`FunctionTrait`] impl
+/// is generated per `"type": "function"` entry, built from that entry's
+/// `inputs`/`outputs`/`stateMutability`, so adding or changing a contract
+/// method is a matter of editing the ABI file, not hand-rolling an
+/// `ethabi::Function` descriptor.
+const ABI_PATH: &str = "abi/Core.json";
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", ABI_PATH);
+
+    let abi = fs::read_to_string(ABI_PATH).expect("read contract ABI");
+    let abi: Vec<Value> = serde_json::from_str(&abi).expect("parse contract ABI");
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+
+    for entry in &abi {
+        if entry.get("type").and_then(Value::as_str) != Some("function") {
+            continue;
+        }
+
+        let name = entry["name"].as_str().expect("function name");
+        let state_mutability = state_mutability_variant(&entry["stateMutability"]);
+        let inputs = params_tokens(&entry["inputs"]);
+        let outputs = params_tokens(&entry["outputs"]);
+        let signature = signature_tokens(&entry["outputs"]);
+
+        let source = format!(
+            r#"use chain_data::FunctionTrait;
+use ethabi::{{Function, Param, ParamType, StateMutability}};
+
+pub struct {type_name}Function;
+
+impl FunctionTrait for {type_name}Function {{
+    fn function() -> Function {{
+        #[allow(deprecated)]
+        Function {{
+            name: "{name}".to_string(),
+            inputs: vec![{inputs}],
+            outputs: vec![{outputs}],
+            constant: None,
+            state_mutability: StateMutability::{state_mutability},
+        }}
+    }}
+
+    fn signature() -> Vec<ParamType> {{
+        vec![{signature}]
+    }}
+}}
+"#,
+            type_name = pascal_case(name),
+            name = name,
+            inputs = inputs,
+            outputs = outputs,
+            signature = signature,
+            state_mutability = state_mutability,
+        );
+
+        let out_path = Path::new(&out_dir).join(format!("{name}.rs"));
+        fs::write(out_path, source).expect("write generated function binding");
+    }
+}
+
+fn pascal_case(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+fn state_mutability_variant(value: &Value) -> &'static str {
+    match value.as_str().expect("stateMutability is a string") {
+        "pure" => "Pure",
+        "view" => "View",
+        "payable" => "Payable",
+        _ => "NonPayable",
+    }
+}
+
+fn param_type_tokens(sol_type: &str) -> String {
+    if let Some(bits) = sol_type.strip_prefix("uint") {
+        return format!("ParamType::Uint({})", if bits.is_empty() { 256 } else { bits.parse().unwrap() });
+    }
+    if let Some(bits) = sol_type.strip_prefix("int") {
+        return format!("ParamType::Int({})", if bits.is_empty() { 256 } else { bits.parse().unwrap() });
+    }
+    if let Some(len) = sol_type.strip_prefix("bytes").filter(|s| !s.is_empty()) {
+        return format!("ParamType::FixedBytes({len})");
+    }
+    match sol_type {
+        "address" => "ParamType::Address".to_string(),
+        "bool" => "ParamType::Bool".to_string(),
+        "string" => "ParamType::String".to_string(),
+        "bytes" => "ParamType::Bytes".to_string(),
+        other => panic!("unsupported Solidity ABI type: {other}"),
+    }
+}
+
+fn params_tokens(params: &Value) -> String {
+    params
+        .as_array()
+        .expect("params is an array")
+        .iter()
+        .map(|param| {
+            let name = param["name"].as_str().unwrap_or_default();
+            let kind = param_type_tokens(param["type"].as_str().expect("param type"));
+            format!(
+                r#"Param {{ name: "{name}".to_string(), kind: {kind}, internal_type: None }}"#
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn signature_tokens(outputs: &Value) -> String {
+    outputs
+        .as_array()
+        .expect("outputs is an array")
+        .iter()
+        .map(|param| param_type_tokens(param["type"].as_str().expect("param type")))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
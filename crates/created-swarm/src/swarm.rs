@@ -26,6 +26,9 @@ use fluence_libp2p::{build_memory_transport, build_transport, RandomPeerId, Tran
 use fs_utils::{make_tmp_dir_peer_id, to_abs_path};
 use particle_node::{Connectivity, Node};
 use particle_protocol::ProtocolConfig;
+use prometheus::Registry;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use script_storage::ScriptStorageConfig;
 use script_storage::{ScriptStorageApi, ScriptStorageBackend};
 use server_config::{BootstrapConfig, NetworkConfig, ServicesConfig};
@@ -36,14 +39,171 @@ use trust_graph::{Certificate, InMemoryStorage, TrustGraph};
 use async_std::task;
 use derivative::Derivative;
 use futures::channel::mpsc::unbounded;
-use futures::{stream::iter, StreamExt};
+use futures::channel::oneshot;
+use futures::future::{select, BoxFuture, Either, Shared};
+use futures::{stream::iter, FutureExt, StreamExt};
 use libp2p::core::multiaddr::Protocol;
+use libp2p::core::muxing::StreamMuxerBox;
+use libp2p::core::transport::Boxed;
+use libp2p::Transport as Libp2pTransport;
 use libp2p::{core::Multiaddr, identity::Keypair, PeerId};
 
+use once_cell::sync::Lazy;
+
+use std::collections::{HashMap, HashSet};
 use std::convert::identity;
+use std::io;
 use std::path::Path;
+use std::sync::{Arc, Mutex};
 use std::{path::PathBuf, time::Duration};
 
+/// Spawns futures on a caller-provided async runtime, so swarm background
+/// tasks aren't tied to a particular executor.
+///
+/// Mirrors the `Executor` abstraction libp2p's `SwarmBuilder` uses to stay
+/// agnostic between `async-std` and `tokio`.
+pub trait Executor: Send + Sync {
+    fn exec(&self, future: BoxFuture<'static, ()>);
+}
+
+impl std::fmt::Debug for dyn Executor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Executor")
+    }
+}
+
+/// Spawns onto the global `async-std` runtime; the default executor, matching
+/// nox's historical behaviour.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AsyncStdExecutor;
+
+impl Executor for AsyncStdExecutor {
+    fn exec(&self, future: BoxFuture<'static, ()>) {
+        task::spawn(future);
+    }
+}
+
+/// Spawns onto the ambient `tokio` runtime. `exec` must be called from within
+/// a tokio runtime context, same requirement as `tokio::spawn` itself.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TokioExecutor;
+
+impl Executor for TokioExecutor {
+    fn exec(&self, future: BoxFuture<'static, ()>) {
+        tokio::spawn(future);
+    }
+}
+
+/// Capability/version record a node advertises about itself, signed with the
+/// node keypair so the advertised identity is verifiable by whoever reads
+/// it, rather than merely asserted. Records are published into the
+/// process-wide [`NODE_INFO_CACHE`]; they are not sent over the network.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NodeInfo {
+    pub peer_id: PeerId,
+    pub node_version: String,
+    pub air_interpreter_version: String,
+    pub enabled_builtins: Vec<String>,
+    pub services: Vec<String>,
+    public_key: libp2p::identity::PublicKey,
+    signature: Vec<u8>,
+}
+
+impl NodeInfo {
+    fn signing_bytes(
+        peer_id: &PeerId,
+        node_version: &str,
+        air_interpreter_version: &str,
+        enabled_builtins: &[String],
+        services: &[String],
+    ) -> Vec<u8> {
+        let mut bytes = peer_id.to_bytes();
+        bytes.extend_from_slice(node_version.as_bytes());
+        bytes.extend_from_slice(air_interpreter_version.as_bytes());
+        for builtin in enabled_builtins {
+            bytes.extend_from_slice(builtin.as_bytes());
+        }
+        for service in services {
+            bytes.extend_from_slice(service.as_bytes());
+        }
+        bytes
+    }
+
+    /// Builds and signs a `NodeInfo` with `keypair`, the way a node signs its
+    /// own handshake payload before advertising it.
+    fn signed(
+        keypair: &Keypair,
+        node_version: String,
+        air_interpreter_version: String,
+        enabled_builtins: Vec<String>,
+        services: Vec<String>,
+    ) -> Self {
+        let peer_id = to_peer_id(keypair);
+        let bytes = Self::signing_bytes(
+            &peer_id,
+            &node_version,
+            &air_interpreter_version,
+            &enabled_builtins,
+            &services,
+        );
+        let signature = keypair.sign(&bytes).expect("sign node info");
+
+        Self {
+            peer_id,
+            node_version,
+            air_interpreter_version,
+            enabled_builtins,
+            services,
+            public_key: keypair.public(),
+            signature,
+        }
+    }
+
+    /// Verifies that `public_key` both signed this payload and actually
+    /// hashes to the advertised `peer_id` — the two checks together are what
+    /// make the claimed identity verifiable rather than just asserted.
+    pub fn verify(&self) -> bool {
+        if PeerId::from(self.public_key.clone()) != self.peer_id {
+            return false;
+        }
+
+        let bytes = Self::signing_bytes(
+            &self.peer_id,
+            &self.node_version,
+            &self.air_interpreter_version,
+            &self.enabled_builtins,
+            &self.services,
+        );
+        self.public_key.verify(&bytes, &self.signature)
+    }
+}
+
+/// Process-wide cache of signed [`NodeInfo`] handshakes, keyed by peer id.
+///
+/// Each node inserts its own signed record as soon as it's constructed (the
+/// "fill in fields locally" part of the handshake), so any other swarm built
+/// in the same process can look it up once connected. No wire protocol
+/// exchanges these records and the connection pool does not hold them, so
+/// only swarms built in the same process can discover each other's
+/// `NodeInfo`; `created-swarm` builds all of its swarms in-process.
+static NODE_INFO_CACHE: Lazy<Mutex<HashMap<PeerId, NodeInfo>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn enabled_builtins(builtins_dir: Option<&Path>) -> Vec<String> {
+    let Some(dir) = builtins_dir else {
+        return Vec::new();
+    };
+
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 #[derive(Derivative)]
 #[derivative(Debug)]
 pub struct CreatedSwarm {
@@ -58,6 +218,29 @@ pub struct CreatedSwarm {
     pub outlet: OneshotOutlet<()>,
     // node connectivity
     pub connectivity: Connectivity,
+    // shared metrics registry, so integration tests can assert on scraped values
+    pub metrics_registry: Option<Registry>,
+}
+
+impl CreatedSwarm {
+    /// Looks up the signed capability/version handshake `peer` advertised,
+    /// verifying its signature before returning it. Returns `None` if `peer`
+    /// isn't currently connected to this swarm, hasn't inserted a record
+    /// yet, or if the signature doesn't check out.
+    ///
+    /// The connectivity check is what keeps this a real discovery lookup
+    /// rather than a process-wide directory: two swarms built in the same
+    /// test binary that were never dialed can't see each other's `NodeInfo`,
+    /// even though both records sit in the same cache.
+    pub async fn node_info(&self, peer: PeerId) -> Option<NodeInfo> {
+        let pool: &ConnectionPoolApi = self.connectivity.as_ref();
+        if !pool.is_connected(peer).await {
+            return None;
+        }
+
+        let info = NODE_INFO_CACHE.lock().unwrap().get(&peer).cloned()?;
+        info.verify().then_some(info)
+    }
 }
 
 pub fn make_swarms(n: usize) -> Vec<CreatedSwarm> {
@@ -114,6 +297,25 @@ where
     )
 }
 
+/// Builds `n` swarms over the memory transport, all sharing one
+/// [`NetworkSim`], and hands back the handle so tests can script partitions
+/// (and heal them) while the swarm is running:
+///
+/// ```ignore
+/// let (swarms, sim) = make_swarms_with_network_sim(3, SimConfig { drop_probability: 0.1, ..<_>::default() });
+/// sim.partition(swarms[0].peer_id, swarms[1].peer_id);
+/// // ... assert on split-brain behavior ...
+/// sim.heal(swarms[0].peer_id, swarms[1].peer_id);
+/// ```
+pub fn make_swarms_with_network_sim(n: usize, sim_config: SimConfig) -> (Vec<CreatedSwarm>, NetworkSim) {
+    let sim = NetworkSim::new(sim_config);
+    let infos = make_swarms_with_cfg(n, {
+        let sim = sim.clone();
+        move |cfg| cfg.with_network_sim(sim.clone())
+    });
+    (infos, sim)
+}
+
 pub fn make_swarms_with_keypair(n: usize, keypair: Keypair) -> Vec<CreatedSwarm> {
     make_swarms_with_cfg(n, |mut cfg| {
         cfg.keypair = keypair.clone();
@@ -135,20 +337,45 @@ pub fn make_swarms_with_builtins(
     })
 }
 
-pub fn make_swarms_with<RT: AquaRuntime, F, M, B>(
+/// Builds a future that resolves once every pool in `pools` has at least as
+/// many connections as its bootstrap count. Doesn't block on any particular
+/// runtime, so callers can `.await` it directly or hand it to an `Executor`.
+fn wait_connected(pools: Vec<(Connectivity, usize)>) -> BoxFuture<'static, ()> {
+    let pools = iter(pools);
+    pools
+        .for_each_concurrent(None, |(pool, bootstraps_num)| async move {
+            let pool = AsRef::<ConnectionPoolApi>::as_ref(&pool);
+            let mut events = pool.lifecycle_events();
+            loop {
+                let num = pool.count_connections().await;
+                if num >= bootstraps_num {
+                    break;
+                }
+                // wait until something changes
+                events.next().await;
+            }
+        })
+        .boxed()
+}
+
+/// Shared node-construction step behind both [`make_swarms_with`] and
+/// [`make_swarms_with_executor`]: picks an address per node, resolves its
+/// bootstraps, and calls `create_node`. Factored out so the two entry points
+/// only differ in how they call `create_node` and how they wait for
+/// connectivity, not in how nodes get built.
+fn build_nodes<RT: AquaRuntime, F, M, B>(
     n: usize,
     mut create_node: F,
     mut create_maddr: M,
     mut bootstraps: B,
-    wait_connected: bool,
-) -> Vec<CreatedSwarm>
+) -> Vec<((PeerId, Keypair, SwarmConfig), Box<Node<RT>>, usize)>
 where
     F: FnMut(Vec<Multiaddr>, Multiaddr) -> (PeerId, Box<Node<RT>>, Keypair, SwarmConfig),
     M: FnMut() -> Multiaddr,
     B: FnMut(Vec<Multiaddr>) -> Vec<Multiaddr>,
 {
     let addrs = (0..n).map(|_| create_maddr()).collect::<Vec<_>>();
-    let nodes = addrs
+    addrs
         .iter()
         .map(|addr| {
             #[rustfmt::skip]
@@ -158,70 +385,168 @@ where
             let (id, node, m_kp, config) = create_node(bootstraps, addr.clone());
             ((id, m_kp, config), node, bootstraps_num)
         })
-        .collect::<Vec<_>>();
+        .collect::<Vec<_>>()
+}
+
+/// Starts `node`, deploys its builtins if configured, and assembles the
+/// [`CreatedSwarm`] handle for it. The other half of the factoring shared by
+/// [`make_swarms_with`] and [`make_swarms_with_executor`].
+fn finalize_node<RT: AquaRuntime>(
+    peer_id: PeerId,
+    management_keypair: Keypair,
+    config: SwarmConfig,
+    node: Box<Node<RT>>,
+) -> CreatedSwarm {
+    let connectivity = node.network_api.connectivity();
+    let stepper = node.aquamarine_api.clone();
+    let startup_peer_id = node.startup_management_peer_id;
+    let local_peer_id = node.local_peer_id;
+    let real_outlet = node.start();
 
-    let pools = iter(
+    // Tee the node's stop signal: `CreatedSwarm::outlet` is the sender we
+    // hand back to the caller, but the metrics-refresh task below also
+    // needs to know when the node stops, and a oneshot only has one
+    // receiver. `shared()` lets the forwarding task and the metrics task
+    // both await the same signal, so sending (or dropping) `outlet` still
+    // stops the node exactly as before, and stops the metrics task with it.
+    let (outlet, stop_signal) = oneshot::channel();
+    let stop_signal = stop_signal.map(|_| ()).shared();
+    config.executor.exec({
+        let stop_signal = stop_signal.clone();
+        async move {
+            stop_signal.await;
+            let _ = real_outlet.send(());
+        }
+        .boxed()
+    });
+
+    if let Some(registry) = config.metrics_registry.as_ref() {
+        let metrics = SwarmMetrics::register(registry);
+        let connectivity = connectivity.clone();
+        let stop_signal = stop_signal.clone();
+        config.executor.exec(
+            async move {
+                let pool: &ConnectionPoolApi = connectivity.as_ref();
+                let mut events = pool.lifecycle_events();
+                loop {
+                    metrics.connections.set(pool.count_connections().await as i64);
+                    // Race the next lifecycle event against the node's stop
+                    // signal, and also stop if the event stream itself ends
+                    // (the pool shutting down), instead of spinning on a
+                    // closed stream or blocking past node shutdown forever.
+                    match select(events.next(), stop_signal.clone()).await {
+                        Either::Left((Some(_), _)) => continue,
+                        Either::Left((None, _)) | Either::Right(_) => break,
+                    }
+                }
+            }
+            .boxed(),
+        );
+    }
+
+    if let Some(builtins_dir) = config.builtins_dir {
+        let mut builtin_loader = BuiltinsDeployer::new(
+            startup_peer_id,
+            local_peer_id,
+            stepper,
+            builtins_dir,
+            Duration::from_millis(PARTICLE_TTL as u64),
+            false,
+        );
+
+        builtin_loader
+            .deploy_builtin_services()
+            .expect("builtins deployed");
+    }
+
+    CreatedSwarm {
+        peer_id,
+        multiaddr: config.listen_on,
+        tmp_dir: config.tmp_dir.unwrap(),
+        management_keypair,
+        outlet,
+        connectivity,
+        metrics_registry: config.metrics_registry,
+    }
+}
+
+pub fn make_swarms_with<RT: AquaRuntime, F, M, B>(
+    n: usize,
+    create_node: F,
+    create_maddr: M,
+    bootstraps: B,
+    wait_connected_flag: bool,
+) -> Vec<CreatedSwarm>
+where
+    F: FnMut(Vec<Multiaddr>, Multiaddr) -> (PeerId, Box<Node<RT>>, Keypair, SwarmConfig),
+    M: FnMut() -> Multiaddr,
+    B: FnMut(Vec<Multiaddr>) -> Vec<Multiaddr>,
+{
+    let nodes = build_nodes(n, create_node, create_maddr, bootstraps);
+
+    let connected = wait_connected(
         nodes
             .iter()
             .map(|(_, n, bootstraps_num)| (n.network_api.connectivity(), *bootstraps_num))
             .collect::<Vec<_>>(),
     );
-    let connected = pools.for_each_concurrent(None, |(pool, bootstraps_num)| async move {
-        let pool = AsRef::<ConnectionPoolApi>::as_ref(&pool);
-        let mut events = pool.lifecycle_events();
-        loop {
-            let num = pool.count_connections().await;
-            if num >= bootstraps_num {
-                break;
-            }
-            // wait until something changes
-            events.next().await;
-        }
-    });
 
     // start all nodes
     let infos = nodes
         .into_iter()
         .map(|((peer_id, management_keypair, config), node, _)| {
-            let connectivity = node.network_api.connectivity();
-            let stepper = node.aquamarine_api.clone();
-            let startup_peer_id = node.startup_management_peer_id;
-            let local_peer_id = node.local_peer_id;
-            let outlet = node.start();
-
-            if let Some(builtins_dir) = config.builtins_dir {
-                let mut builtin_loader = BuiltinsDeployer::new(
-                    startup_peer_id,
-                    local_peer_id,
-                    stepper,
-                    builtins_dir,
-                    Duration::from_millis(PARTICLE_TTL as u64),
-                    false,
-                );
-
-                builtin_loader
-                    .deploy_builtin_services()
-                    .expect("builtins deployed");
-            }
-
-            CreatedSwarm {
-                peer_id,
-                multiaddr: config.listen_on,
-                tmp_dir: config.tmp_dir.unwrap(),
-                management_keypair,
-                outlet,
-                connectivity,
-            }
+            finalize_node(peer_id, management_keypair, config, node)
         })
         .collect();
 
-    if wait_connected {
+    if wait_connected_flag {
         task::block_on(connected);
     }
 
     infos
 }
 
+/// Non-blocking counterpart of [`make_swarms_with`]: instead of parking the
+/// current thread in `task::block_on`, it hands back the "wait until
+/// connected" step as a future the caller awaits on whatever runtime is
+/// driving it. This is what lets nox be embedded in a tokio host without
+/// spinning up a nested async-std runtime just to wait for swarm readiness.
+pub fn make_swarms_with_executor<RT: AquaRuntime, F, M, B>(
+    n: usize,
+    executor: Arc<dyn Executor>,
+    mut create_node: F,
+    create_maddr: M,
+    bootstraps: B,
+) -> (Vec<CreatedSwarm>, BoxFuture<'static, ()>)
+where
+    F: FnMut(Vec<Multiaddr>, Multiaddr, Arc<dyn Executor>) -> (PeerId, Box<Node<RT>>, Keypair, SwarmConfig),
+    M: FnMut() -> Multiaddr,
+    B: FnMut(Vec<Multiaddr>) -> Vec<Multiaddr>,
+{
+    let nodes = build_nodes(
+        n,
+        |bs, maddr| create_node(bs, maddr, executor.clone()),
+        create_maddr,
+        bootstraps,
+    );
+
+    let connected = wait_connected(
+        nodes
+            .iter()
+            .map(|(_, n, bootstraps_num)| (n.network_api.connectivity(), *bootstraps_num))
+            .collect::<Vec<_>>(),
+    );
+
+    let infos = nodes
+        .into_iter()
+        .map(|((peer_id, management_keypair, config), node, _)| {
+            finalize_node(peer_id, management_keypair, config, node)
+        })
+        .collect();
+
+    (infos, connected)
+}
+
 #[derive(Default, Clone, Debug)]
 pub struct Trust {
     pub root_weights: Vec<(fluence_identity::PublicKey, u32)>,
@@ -229,6 +554,235 @@ pub struct Trust {
     pub cur_time: Duration,
 }
 
+/// Deterministic fault-injection parameters for [`NetworkSim`]: per-link
+/// latency/jitter, drop probability, and a seed so a run can be reproduced
+/// exactly.
+#[derive(Clone, Debug)]
+pub struct SimConfig {
+    pub base_latency: Duration,
+    pub jitter: Duration,
+    pub drop_probability: f64,
+    pub seed: u64,
+}
+
+impl Default for SimConfig {
+    fn default() -> Self {
+        Self {
+            base_latency: Duration::from_millis(0),
+            jitter: Duration::from_millis(0),
+            drop_probability: 0.0,
+            seed: 0,
+        }
+    }
+}
+
+struct NetworkSimState {
+    // One RNG per link, seeded from (global seed, link key) rather than one
+    // RNG shared across every link: draws for link (A, B) then depend only
+    // on how many times that specific link has been sampled, not on
+    // whichever concurrent connection attempt happens to acquire the lock
+    // first. That's what makes a run reproducible regardless of scheduling.
+    link_rngs: HashMap<(PeerId, PeerId), StdRng>,
+    partitions: HashSet<(PeerId, PeerId)>,
+    // Registered by `create_swarm_with_runtime` for every node built with
+    // this sim, so `partition` can reach into an already-open connection
+    // and close it rather than only affecting connections dialed from now
+    // on.
+    peers: HashMap<PeerId, (Connectivity, Arc<dyn Executor>)>,
+}
+
+/// Models the network between nodes built with the memory transport: every
+/// connection upgrade is delayed, possibly dropped, and checked against
+/// scripted partitions, all driven by a seeded RNG so failures reproduce
+/// exactly. Clone and hand a copy to [`SwarmConfig::network_sim`] on every
+/// node that should share the same simulated network; keep the original to
+/// mutate partitions at runtime.
+#[derive(Clone)]
+pub struct NetworkSim {
+    config: SimConfig,
+    state: Arc<Mutex<NetworkSimState>>,
+}
+
+impl NetworkSim {
+    pub fn new(config: SimConfig) -> Self {
+        Self {
+            config,
+            state: Arc::new(Mutex::new(NetworkSimState {
+                link_rngs: HashMap::new(),
+                partitions: HashSet::new(),
+                peers: HashMap::new(),
+            })),
+        }
+    }
+
+    /// Registers `peer_id`'s connection pool, so a later [`NetworkSim::partition`]
+    /// between `peer_id` and another registered peer can force-close the
+    /// connection between them if one is already open. Called by
+    /// `create_swarm_with_runtime` for every node built with this sim.
+    fn register(&self, peer_id: PeerId, connectivity: Connectivity, executor: Arc<dyn Executor>) {
+        self.state
+            .lock()
+            .unwrap()
+            .peers
+            .insert(peer_id, (connectivity, executor));
+    }
+
+    fn link(a: PeerId, b: PeerId) -> (PeerId, PeerId) {
+        if a.to_bytes() < b.to_bytes() {
+            (a, b)
+        } else {
+            (b, a)
+        }
+    }
+
+    /// Derives this link's RNG seed from the global seed and the link key,
+    /// so every link gets an independent, reproducible draw sequence.
+    fn link_seed(&self, link: (PeerId, PeerId)) -> u64 {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let mut hasher = DefaultHasher::new();
+        self.config.seed.hash(&mut hasher);
+        link.0.hash(&mut hasher);
+        link.1.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Blocks traffic between `a` and `b` until [`NetworkSim::heal`] is
+    /// called, and force-closes the connection between them if one is
+    /// already open. Without the force-close, partitioning two nodes that
+    /// are already connected (the documented `make_swarms_with_network_sim`
+    /// usage, since `make_swarms_with_cfg` blocks until bootstrap
+    /// connections are up before returning) would be a no-op: the
+    /// transport-upgrade check this sim installs only ever sees *new*
+    /// dial attempts.
+    pub fn partition(&self, a: PeerId, b: PeerId) {
+        let mut state = self.state.lock().unwrap();
+        state.partitions.insert(Self::link(a, b));
+        let side_a = state.peers.get(&a).cloned();
+        let side_b = state.peers.get(&b).cloned();
+        drop(state);
+
+        if let Some((connectivity, executor)) = side_a {
+            executor.exec(Self::disconnect(connectivity, b));
+        }
+        if let Some((connectivity, executor)) = side_b {
+            executor.exec(Self::disconnect(connectivity, a));
+        }
+    }
+
+    fn disconnect(connectivity: Connectivity, peer: PeerId) -> BoxFuture<'static, ()> {
+        async move {
+            let pool: &ConnectionPoolApi = connectivity.as_ref();
+            pool.disconnect(peer).await;
+        }
+        .boxed()
+    }
+
+    /// Heals a previously scripted partition between `a` and `b`. Doesn't
+    /// redial itself — it just stops blocking the next dial attempt, same
+    /// as the node's existing bootstrap/reconnect logic would make after
+    /// any other connection loss.
+    pub fn heal(&self, a: PeerId, b: PeerId) {
+        self.state.lock().unwrap().partitions.remove(&Self::link(a, b));
+    }
+
+    fn is_partitioned(&self, a: PeerId, b: PeerId) -> bool {
+        self.state.lock().unwrap().partitions.contains(&Self::link(a, b))
+    }
+
+    /// Runs `f` against the RNG dedicated to the link between `a` and `b`,
+    /// lazily seeding it on first use.
+    fn with_link_rng<R>(&self, a: PeerId, b: PeerId, f: impl FnOnce(&mut StdRng) -> R) -> R {
+        let link = Self::link(a, b);
+        let seed = self.link_seed(link);
+        let mut state = self.state.lock().unwrap();
+        let rng = state
+            .link_rngs
+            .entry(link)
+            .or_insert_with(|| StdRng::seed_from_u64(seed));
+        f(rng)
+    }
+
+    async fn delay(&self, a: PeerId, b: PeerId) {
+        let jitter_ms = self.config.jitter.as_millis() as u64;
+        let extra = if jitter_ms == 0 {
+            0
+        } else {
+            self.with_link_rng(a, b, |rng| rng.gen_range(0..=jitter_ms))
+        };
+        let wait = self.config.base_latency + Duration::from_millis(extra);
+        if !wait.is_zero() {
+            task::sleep(wait).await;
+        }
+    }
+
+    fn should_drop(&self, a: PeerId, b: PeerId) -> bool {
+        if self.config.drop_probability <= 0.0 {
+            return false;
+        }
+        self.with_link_rng(a, b, |rng| rng.gen::<f64>()) < self.config.drop_probability
+    }
+}
+
+/// Wraps a connection upgrade future with [`NetworkSim`]'s latency, drop and
+/// partition checks before handing the negotiated muxer back to the swarm.
+/// Only meaningful for the memory transport, since it's the only one whose
+/// links are entirely within this process and thus simulatable.
+fn wrap_with_network_sim(
+    transport: Boxed<(PeerId, StreamMuxerBox)>,
+    local_peer_id: PeerId,
+    sim: NetworkSim,
+) -> Boxed<(PeerId, StreamMuxerBox)> {
+    Libp2pTransport::and_then(transport, move |(peer_id, muxer), endpoint| {
+        let sim = sim.clone();
+        async move {
+            // The upgrade future this closure wraps runs independently on
+            // both ends of a connection. Only inject the fault on the
+            // dialer side, or a single link's configured latency/drop
+            // probability would silently double.
+            if !endpoint.is_dialer() {
+                return Ok((peer_id, muxer));
+            }
+
+            sim.delay(local_peer_id, peer_id).await;
+            if sim.should_drop(local_peer_id, peer_id) || sim.is_partitioned(local_peer_id, peer_id) {
+                return Err(io::Error::new(
+                    io::ErrorKind::ConnectionRefused,
+                    "blocked by NetworkSim",
+                ));
+            }
+            Ok((peer_id, muxer))
+        }
+    })
+    .boxed()
+}
+
+/// Metrics this crate registers into `metrics_registry` directly: the active
+/// connections gauge already tracked by `count_connections`. Particle
+/// throughput, AIR interpreter execution latency, and VM pool queue depth
+/// are registered against the same shared registry by `ScriptStorageBackend`
+/// and `VmPoolConfig` themselves (they're handed `config.metrics_registry`
+/// below), since that's where those numbers are actually produced.
+struct SwarmMetrics {
+    connections: prometheus::IntGauge,
+}
+
+impl SwarmMetrics {
+    fn register(registry: &Registry) -> Self {
+        let connections = prometheus::IntGauge::new(
+            "nox_connections",
+            "Number of currently open connections on this node",
+        )
+        .expect("create connections gauge");
+        registry
+            .register(Box::new(connections.clone()))
+            .expect("register connections gauge");
+
+        Self { connections }
+    }
+}
+
 #[derive(Clone, Derivative)]
 #[derivative(Debug)]
 pub struct SwarmConfig {
@@ -241,6 +795,32 @@ pub struct SwarmConfig {
     pub tmp_dir: Option<PathBuf>,
     pub pool_size: Option<usize>,
     pub builtins_dir: Option<PathBuf>,
+    /// Addresses peers should actually dial to reach this node, e.g. a
+    /// public host/port sitting in front of a NATed or port-forwarded
+    /// `listen_on`. Registered as external addresses on the swarm and
+    /// advertised over identify and Kademlia.
+    pub external_addresses: Vec<Multiaddr>,
+    /// Shared registry metrics are recorded into: this crate registers an
+    /// active-connections gauge directly ([`SwarmMetrics`]), and hands the
+    /// same registry to `ScriptStorageBackend` and `VmPoolConfig` so they can
+    /// register particle throughput, AIR interpreter execution latency, and
+    /// VM pool queue depth against it. `None` disables metrics collection,
+    /// matching the previous hardcoded behavior.
+    pub metrics_registry: Option<Registry>,
+    /// Services advertised in this node's [`NodeInfo`] handshake, in addition
+    /// to whatever builtins `builtins_dir` deploys.
+    pub advertised_services: Vec<String>,
+    /// Deterministic network fault injection, applied to the memory
+    /// transport only. Share the same [`NetworkSim`] across every node in a
+    /// topology so partitions are consistent from both sides of a link.
+    #[derivative(Debug = "ignore")]
+    pub network_sim: Option<NetworkSim>,
+    /// Runtime background tasks (transport upgrades, node loops) are spawned
+    /// on. Defaults to [`AsyncStdExecutor`] to keep existing callers working;
+    /// pass [`TokioExecutor`] (or your own impl) via [`SwarmConfig::with_executor`]
+    /// to embed nox in a tokio host.
+    #[derivative(Debug = "ignore")]
+    pub executor: Arc<dyn Executor>,
 }
 
 impl SwarmConfig {
@@ -258,9 +838,64 @@ impl SwarmConfig {
             tmp_dir: None,
             pool_size: <_>::default(),
             builtins_dir: None,
+            external_addresses: <_>::default(),
+            metrics_registry: None,
+            advertised_services: <_>::default(),
+            network_sim: None,
+            executor: Arc::new(AsyncStdExecutor),
         }
     }
 
+    pub fn with_network_sim(mut self, network_sim: NetworkSim) -> Self {
+        self.network_sim = Some(network_sim);
+        self
+    }
+
+    pub fn with_metrics_registry(mut self, metrics_registry: Registry) -> Self {
+        self.metrics_registry = Some(metrics_registry);
+        self
+    }
+
+    pub fn with_advertised_services(mut self, services: Vec<String>) -> Self {
+        self.advertised_services = services;
+        self
+    }
+
+    pub fn with_executor(mut self, executor: Arc<dyn Executor>) -> Self {
+        self.executor = executor;
+        self
+    }
+
+    pub fn with_external_addresses(mut self, external_addresses: Vec<Multiaddr>) -> Self {
+        self.external_addresses = external_addresses;
+        self
+    }
+
+    /// Advertises `host` as the dialable address for this node, reusing the
+    /// port `listen_on` already binds to. Convenient when the operator knows
+    /// the public host but the bind port and the advertised port are the same,
+    /// e.g. a straightforward port-forward.
+    pub fn with_external_host(mut self, host: Multiaddr) -> Self {
+        assert!(
+            !host.iter().any(|p| matches!(p, Protocol::Tcp(_))),
+            "with_external_host expects a portless host address (got {host}); \
+             use with_external_addresses to advertise a fully-formed multiaddr instead"
+        );
+
+        let port = self.listen_on.iter().find_map(|p| match p {
+            Protocol::Tcp(port) => Some(port),
+            _ => None,
+        });
+
+        let mut external = host;
+        if let Some(port) = port {
+            external.push(Protocol::Tcp(port));
+        }
+
+        self.external_addresses.push(external);
+        self
+    }
+
     pub fn with_trust(bootstraps: Vec<Multiaddr>, listen_on: Multiaddr, trust: Trust) -> Self {
         let mut this = Self::new(bootstraps, listen_on);
         this.trust = Some(trust);
@@ -323,6 +958,17 @@ pub fn create_swarm_with_runtime<RT: AquaRuntime>(
 
     let peer_id = to_peer_id(&config.keypair);
 
+    let node_info = NodeInfo::signed(
+        &config.keypair,
+        env!("CARGO_PKG_VERSION").to_string(),
+        option_env!("AIR_INTERPRETER_VERSION")
+            .unwrap_or("unknown")
+            .to_string(),
+        enabled_builtins(config.builtins_dir.as_deref()),
+        config.advertised_services.clone(),
+    );
+    NODE_INFO_CACHE.lock().unwrap().insert(peer_id, node_info);
+
     if config.tmp_dir.is_none() {
         config.tmp_dir = Some(make_tmp_dir_peer_id(peer_id.to_string()));
     }
@@ -352,7 +998,9 @@ pub fn create_swarm_with_runtime<RT: AquaRuntime>(
         trust_graph,
         bootstrap_nodes: bootstraps.clone(),
         bootstrap: BootstrapConfig::zero(),
-        registry: None,
+        external_addresses: config.external_addresses.clone(),
+        advertised_services: config.advertised_services.clone(),
+        registry: config.metrics_registry.clone(),
         protocol_config,
         kademlia_config: Default::default(),
         particle_queue_buffer: 100,
@@ -363,14 +1011,41 @@ pub fn create_swarm_with_runtime<RT: AquaRuntime>(
     };
 
     let transport = match transport {
-        Transport::Memory => build_memory_transport(config.keypair.clone(), TRANSPORT_TIMEOUT),
-        Transport::Network => build_transport(config.keypair.clone(), TRANSPORT_TIMEOUT),
+        Transport::Memory => {
+            let transport = build_memory_transport(
+                config.keypair.clone(),
+                TRANSPORT_TIMEOUT,
+                config.executor.clone(),
+            );
+            match config.network_sim.clone() {
+                Some(sim) => wrap_with_network_sim(transport, peer_id, sim),
+                None => transport,
+            }
+        }
+        Transport::Network => build_transport(
+            config.keypair.clone(),
+            TRANSPORT_TIMEOUT,
+            config.executor.clone(),
+        ),
     };
 
-    let (swarm, network_api) =
+    let (mut swarm, network_api) =
         Node::swarm(peer_id, network_config, transport, vec![listen_on.clone()]);
 
+    for addr in &config.external_addresses {
+        swarm.add_external_address(addr.clone(), libp2p::swarm::AddressScore::Infinite);
+    }
+
     let connectivity = network_api.connectivity();
+
+    if let Some(sim) = config.network_sim.as_ref() {
+        sim.register(peer_id, connectivity.clone(), config.executor.clone());
+    }
+
+    // The metrics-refresh task is spawned in `finalize_node` instead of here,
+    // once `node.start()` has handed back a stop signal it can be raced
+    // against — starting it this early would leak it past node shutdown.
+
     let (particle_failures_out, particle_failures_in) = unbounded();
     let (script_storage_api, script_storage_backend) = {
         let script_storage_config = ScriptStorageConfig {
@@ -381,11 +1056,17 @@ pub fn create_swarm_with_runtime<RT: AquaRuntime>(
         };
 
         let pool: &ConnectionPoolApi = connectivity.as_ref();
-        ScriptStorageBackend::new(pool.clone(), particle_failures_in, script_storage_config)
+        ScriptStorageBackend::new(
+            pool.clone(),
+            particle_failures_in,
+            script_storage_config,
+            config.metrics_registry.as_ref(),
+        )
     };
 
     let pool_size = config.pool_size.unwrap_or(1);
-    let pool_config = VmPoolConfig::new(pool_size, EXECUTION_TIMEOUT);
+    let pool_config =
+        VmPoolConfig::new(pool_size, EXECUTION_TIMEOUT, config.metrics_registry.as_ref());
 
     std::fs::create_dir_all(tmp_dir).expect("create tmp dir");
 
@@ -414,6 +1095,7 @@ pub fn create_swarm_with_runtime<RT: AquaRuntime>(
         "0.0.0.0:0".parse().unwrap(),
         startup_management_peer_id,
         bootstraps,
+        config.executor.clone(),
     );
 
     node.listen(vec![listen_on]).expect("listen");
@@ -423,4 +1105,77 @@ pub fn create_swarm_with_runtime<RT: AquaRuntime>(
 
 pub fn create_swarm(config: SwarmConfig) -> (PeerId, Box<Node<AVM>>, Keypair, SwarmConfig) {
     create_swarm_with_runtime(config, aqua_vm_config)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[async_std::test]
+    async fn node_info_discovered_after_connect() {
+        let swarms = make_swarms(2);
+
+        let info = swarms[0]
+            .node_info(swarms[1].peer_id)
+            .await
+            .expect("peer is connected, info should be discoverable");
+        assert_eq!(info.peer_id, swarms[1].peer_id);
+        assert!(info.verify());
+    }
+
+    #[async_std::test]
+    async fn node_info_hidden_for_unconnected_peer() {
+        // Two independent pairs: first_pair[0] was never dialed by
+        // second_pair[0], even though both records sit
+        // in the same process-wide `NODE_INFO_CACHE`.
+        let first_pair = make_swarms(2);
+        let second_pair = make_swarms(2);
+
+        assert!(first_pair[0]
+            .node_info(second_pair[0].peer_id)
+            .await
+            .is_none());
+    }
+
+    #[async_std::test]
+    async fn metrics_registry_observes_connection_count() {
+        let registry = Registry::new();
+        let swarms = make_swarms_with_cfg(2, {
+            let registry = registry.clone();
+            move |cfg| cfg.with_metrics_registry(registry.clone())
+        });
+
+        // Give the metrics-refresh task a chance to react to the lifecycle
+        // event the already-established bootstrap connection fired.
+        let gauge = loop {
+            let families = registry.gather();
+            let gauge = families
+                .iter()
+                .find(|f| f.get_name() == "nox_connections")
+                .map(|f| f.get_metric()[0].get_gauge().get_value());
+            match gauge {
+                Some(value) if value > 0.0 => break value,
+                _ => task::sleep(Duration::from_millis(20)).await,
+            }
+        };
+        assert!(gauge > 0.0);
+
+        drop(swarms);
+    }
+
+    #[async_std::test]
+    async fn network_sim_partition_closes_live_connection() {
+        let (swarms, sim) = make_swarms_with_network_sim(2, SimConfig::default());
+        let (a, b) = (swarms[0].peer_id, swarms[1].peer_id);
+
+        let pool_a: &ConnectionPoolApi = swarms[0].connectivity.as_ref();
+        assert!(pool_a.is_connected(b).await, "bootstrap connection is up");
+
+        sim.partition(a, b);
+        while pool_a.is_connected(b).await {
+            task::sleep(Duration::from_millis(20)).await;
+        }
+
+        sim.heal(a, b);
+    }
+}